@@ -1,8 +1,230 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use clap::Parser;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command as StdCommand};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::Manager;
+
+const DEFAULT_BACKEND_URL: &str = "http://localhost:8000";
+const DEFAULT_ACTIVITY: &str = "general";
+const DEFAULT_CONFIG_PATH: &str = "config.json";
+
+/// Command-line flags for running the desktop app headless or against a
+/// non-default backend, e.g. in CI or when pointing at a staging server.
+#[derive(Debug, Parser)]
+#[command(version, about = "Weather Predictor desktop app")]
+struct Cli {
+    /// Path to a JSON config file (see `Config`). Defaults to `config.json`.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Overrides `backend_url` from the config file.
+    #[arg(long)]
+    backend_url: Option<String>,
+
+    /// Overrides `openweather_api_key` from the config file.
+    #[arg(long)]
+    api_key: Option<String>,
+}
+
+/// Structured app configuration, loadable from a JSON file and overridable
+/// by CLI flags, so the app can be deployed without recompiling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Config {
+    #[serde(default = "default_backend_url")]
+    backend_url: String,
+    #[serde(default)]
+    openweather_api_key: String,
+    #[serde(default = "default_activity")]
+    default_activity: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            backend_url: default_backend_url(),
+            openweather_api_key: String::new(),
+            default_activity: default_activity(),
+        }
+    }
+}
+
+fn default_backend_url() -> String {
+    DEFAULT_BACKEND_URL.to_string()
+}
+
+fn default_activity() -> String {
+    DEFAULT_ACTIVITY.to_string()
+}
+
+/// Error loading or parsing the config file, with enough detail to show the
+/// user what they need to fix.
+#[derive(Debug)]
+struct ConfigError(String);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Loads `Config` from the file named by `--config` (or `config.json` if
+/// unset, skipped entirely if that file doesn't exist), then layers any
+/// `--backend-url` / `--api-key` CLI overrides on top.
+fn load_config(cli: &Cli) -> Result<Config, ConfigError> {
+    let path = cli
+        .config
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH));
+
+    let mut config = if path.exists() {
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| ConfigError(format!("Failed to read config file {}: {}", path.display(), e)))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| ConfigError(format!("Invalid config file {}: {}", path.display(), e)))?
+    } else {
+        Config::default()
+    };
+
+    if let Some(backend_url) = &cli.backend_url {
+        config.backend_url = backend_url.clone();
+    }
+    if let Some(api_key) = &cli.api_key {
+        config.openweather_api_key = api_key.clone();
+    }
+
+    Ok(config)
+}
+
+/// Shared state for talking to the Python inference backend: a single
+/// `reqwest::Client` (so connections get pooled instead of re-established on
+/// every invoke) plus the backend's base URL, which the frontend can repoint
+/// at a remote server at runtime, plus a handle to the backend process we
+/// spawned (if any) so it can be torn down when the app exits.
+struct AppState {
+    backend_url: String,
+    client: reqwest::Client,
+    backend_process: Option<Child>,
+    history_path: PathBuf,
+    default_activity: String,
+}
+
+/// File name for the local prediction history store, written under the
+/// app's data directory.
+const HISTORY_FILE_NAME: &str = "prediction_history.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HistoryEntry {
+    request: PredictionRequest,
+    response: ApiResponse,
+}
+
+fn read_history(path: &Path) -> Vec<HistoryEntry> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn write_history(path: &Path, entries: &[HistoryEntry]) -> Result<(), String> {
+    let contents =
+        serde_json::to_string_pretty(entries).map_err(|e| format!("Failed to serialize history: {}", e))?;
+    std::fs::write(path, contents).map_err(|e| format!("Failed to write history file: {}", e))
+}
+
+/// Appends a single entry to the history store. The caller must hold the
+/// `AppState` mutex for the duration of this call so a concurrent append or
+/// clear can't read the file out from under it and clobber the write.
+fn append_history(path: &Path, entry: HistoryEntry) {
+    let mut entries = read_history(path);
+    entries.push(entry);
+    if let Err(e) = write_history(path, &entries) {
+        eprintln!("Failed to record prediction history: {}", e);
+    }
+}
+
+/// Directory containing the Python inference backend, resolved relative to
+/// the running executable rather than the process's current working
+/// directory, so a packaged/installed build can find it regardless of where
+/// it was launched from.
+const BACKEND_DIR: &str = "backend";
+
+/// Resolves `BACKEND_DIR` next to the current executable, falling back to a
+/// plain relative path if the executable's location can't be determined.
+fn backend_dir() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(BACKEND_DIR)))
+        .unwrap_or_else(|| PathBuf::from(BACKEND_DIR))
+}
+
+/// Whether `backend_url` points at this machine, i.e. whether it makes
+/// sense to spawn a local sidecar for it at all. A remote/staging/production
+/// `backend_url` should never trigger a local sidecar spawn.
+fn is_local_backend(backend_url: &str) -> bool {
+    reqwest::Url::parse(backend_url)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.to_string()))
+        .map(|host| host == "localhost" || host == "127.0.0.1" || host == "[::1]")
+        .unwrap_or(false)
+}
+
+/// Launches the Python inference backend as a child process so the user
+/// doesn't have to start it manually before opening the app. Failure to
+/// spawn is logged but not fatal - `wait_for_backend` will simply time out
+/// and report a clear error if nothing ever comes up on `backend_url`.
+fn spawn_backend_process() -> Option<Child> {
+    match StdCommand::new("python3")
+        .args(["-m", "uvicorn", "main:app", "--host", "127.0.0.1", "--port", "8000"])
+        .current_dir(backend_dir())
+        .spawn()
+    {
+        Ok(child) => Some(child),
+        Err(e) => {
+            eprintln!("Failed to spawn backend process: {}", e);
+            None
+        }
+    }
+}
+
+/// Polls `{backend_url}/health` with exponential backoff until it succeeds
+/// or `max_attempts` is reached, returning a descriptive error on timeout.
+async fn wait_for_backend(
+    client: &reqwest::Client,
+    backend_url: &str,
+    max_attempts: u32,
+) -> Result<String, String> {
+    let base_delay = Duration::from_millis(250);
+    let max_delay = Duration::from_secs(5);
+
+    for attempt in 0..max_attempts {
+        if let Ok(response) = client.get(format!("{}/health", backend_url)).send().await {
+            if response.status().is_success() {
+                return Ok("Backend is running".to_string());
+            }
+        }
+
+        let delay = base_delay.saturating_mul(2u32.saturating_pow(attempt)).min(max_delay);
+        tokio::time::sleep(delay).await;
+    }
+
+    Err(format!(
+        "Backend at {} did not become healthy after {} attempts",
+        backend_url, max_attempts
+    ))
+}
+
+/// The active OpenWeather API key, set once in `main` from the merged
+/// `Config` so CLI/file overrides are honored everywhere it's read.
+static OPENWEATHER_API_KEY: OnceLock<String> = OnceLock::new();
 
 #[derive(Debug, Serialize, Deserialize)]
 struct WeatherPrediction {
@@ -13,7 +235,7 @@ struct WeatherPrediction {
     very_uncomfortable: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct PredictionRequest {
     temperature: f64,
     humidity: f64,
@@ -22,7 +244,7 @@ struct PredictionRequest {
     activity: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ApiResponse {
     prediction: WeatherPrediction,
     activity_risk: String,
@@ -38,19 +260,28 @@ async fn get_weather_prediction(
     wind_speed: f64,
     pressure: f64,
     activity: String,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<AppState>>,
 ) -> Result<ApiResponse, String> {
-    let client = reqwest::Client::new();
-    
+    let (client, backend_url, default_activity) = {
+        let state = state.lock().map_err(|e| format!("State lock poisoned: {}", e))?;
+        (
+            state.client.clone(),
+            state.backend_url.clone(),
+            state.default_activity.clone(),
+        )
+    };
+
     let request_data = PredictionRequest {
         temperature,
         humidity,
         wind_speed,
         pressure,
-        activity,
+        activity: if activity.is_empty() { default_activity } else { activity },
     };
 
     let response = client
-        .post("http://localhost:8000/predict")
+        .post(format!("{}/predict", backend_url))
         .json(&request_data)
         .send()
         .await
@@ -61,19 +292,253 @@ async fn get_weather_prediction(
             .json()
             .await
             .map_err(|e| format!("Failed to parse response: {}", e))?;
-        
+
+        let entry = HistoryEntry {
+            request: request_data,
+            response: api_response.clone(),
+        };
+        // Re-acquire the same managed mutex on a blocking thread and hold it
+        // for the whole read-modify-write so a concurrent append or clear
+        // can't interleave and clobber this write.
+        tauri::async_runtime::spawn_blocking(move || {
+            let state = app_handle.state::<Mutex<AppState>>();
+            let state = state.lock().expect("State lock poisoned");
+            append_history(&state.history_path, entry);
+        });
+
         Ok(api_response)
     } else {
         Err(format!("API request failed with status: {}", response.status()))
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct OpenWeatherResponse {
+    main: OpenWeatherMain,
+    wind: OpenWeatherWind,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherMain {
+    temp: f64,
+    humidity: f64,
+    pressure: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherWind {
+    speed: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherForecastResponse {
+    list: Vec<OpenWeatherForecastEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherForecastEntry {
+    dt_txt: String,
+    main: OpenWeatherMain,
+    wind: OpenWeatherWind,
+}
+
+/// A single step of a multi-day forecast, pairing the forecast time it's
+/// for with either the resulting prediction or the error that prevented one,
+/// so one bad `/predict` call doesn't discard the rest of the week.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ForecastStep {
+    time: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prediction: Option<ApiResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Returns the configured OpenWeather API key, or a clear error telling the
+/// user how to set one. Shared by every command that calls OpenWeather so
+/// the check and its message don't drift between them.
+fn require_openweather_key() -> Result<String, String> {
+    let api_key = OPENWEATHER_API_KEY.get().cloned().unwrap_or_default();
+
+    if api_key.is_empty() {
+        return Err(
+            "OpenWeather API key not configured; set \"openweather_api_key\" in the config file or pass --api-key"
+                .to_string(),
+        );
+    }
+
+    Ok(api_key)
+}
+
+// Tauri command to auto-fill sensor values from OpenWeather's current
+// conditions for a city, so the user doesn't have to type them in by hand.
+#[tauri::command]
+async fn fetch_current_conditions(
+    city: String,
+    state: tauri::State<'_, Mutex<AppState>>,
+) -> Result<PredictionRequest, String> {
+    let api_key = require_openweather_key()?;
+
+    let client = {
+        let state = state.lock().map_err(|e| format!("State lock poisoned: {}", e))?;
+        state.client.clone()
+    };
+
+    let response = client
+        .get("https://api.openweathermap.org/data/2.5/weather")
+        .query(&[
+            ("q", city.as_str()),
+            ("appid", api_key.as_str()),
+            ("units", "metric"),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach OpenWeather: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "OpenWeather request failed with status: {}",
+            response.status()
+        ));
+    }
+
+    let conditions: OpenWeatherResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse OpenWeather response: {}", e))?;
+
+    Ok(PredictionRequest {
+        temperature: conditions.main.temp,
+        humidity: conditions.main.humidity,
+        wind_speed: conditions.wind.speed,
+        pressure: conditions.main.pressure,
+        activity: String::new(),
+    })
+}
+
+// Tauri command to batch predictions over an OpenWeather 5-day/3-hour
+// forecast, giving a "when is it safe to do this activity this week" view
+// instead of a single point-in-time prediction.
+#[tauri::command]
+async fn forecast_predictions(
+    city: String,
+    activity: String,
+    state: tauri::State<'_, Mutex<AppState>>,
+) -> Result<Vec<ForecastStep>, String> {
+    let api_key = require_openweather_key()?;
+
+    let (client, backend_url, default_activity) = {
+        let state = state.lock().map_err(|e| format!("State lock poisoned: {}", e))?;
+        (state.client.clone(), state.backend_url.clone(), state.default_activity.clone())
+    };
+    let activity = if activity.is_empty() { default_activity } else { activity };
+
+    let response = client
+        .get("https://api.openweathermap.org/data/2.5/forecast")
+        .query(&[
+            ("q", city.as_str()),
+            ("appid", api_key.as_str()),
+            ("units", "metric"),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach OpenWeather: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "OpenWeather request failed with status: {}",
+            response.status()
+        ));
+    }
+
+    let forecast: OpenWeatherForecastResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse OpenWeather response: {}", e))?;
+
+    let steps = forecast.list.into_iter().map(|entry| {
+        let client = client.clone();
+        let backend_url = backend_url.clone();
+        let activity = activity.clone();
+        let time = entry.dt_txt;
+
+        async move {
+            let request_data = PredictionRequest {
+                temperature: entry.main.temp,
+                humidity: entry.main.humidity,
+                wind_speed: entry.wind.speed,
+                pressure: entry.main.pressure,
+                activity,
+            };
+
+            let result = async {
+                let response = client
+                    .post(format!("{}/predict", backend_url))
+                    .json(&request_data)
+                    .send()
+                    .await
+                    .map_err(|e| format!("Failed to send request: {}", e))?;
+
+                if response.status().is_success() {
+                    response
+                        .json::<ApiResponse>()
+                        .await
+                        .map_err(|e| format!("Failed to parse response: {}", e))
+                } else {
+                    Err(format!("API request failed with status: {}", response.status()))
+                }
+            }
+            .await;
+
+            match result {
+                Ok(prediction) => ForecastStep {
+                    time,
+                    prediction: Some(prediction),
+                    error: None,
+                },
+                Err(e) => ForecastStep {
+                    time,
+                    prediction: None,
+                    error: Some(e),
+                },
+            }
+        }
+    });
+
+    Ok(futures::future::join_all(steps).await)
+}
+
+// Tauri command to fetch the most recent prediction history entries, newest
+// first, so the frontend can render past queries and spot trends.
+#[tauri::command]
+fn get_prediction_history(
+    limit: usize,
+    state: tauri::State<'_, Mutex<AppState>>,
+) -> Result<Vec<HistoryEntry>, String> {
+    let state = state.lock().map_err(|e| format!("State lock poisoned: {}", e))?;
+
+    let mut entries = read_history(&state.history_path);
+    entries.reverse();
+    entries.truncate(limit);
+    Ok(entries)
+}
+
+// Tauri command to wipe the local prediction history store.
+#[tauri::command]
+fn clear_prediction_history(state: tauri::State<'_, Mutex<AppState>>) -> Result<(), String> {
+    let state = state.lock().map_err(|e| format!("State lock poisoned: {}", e))?;
+    write_history(&state.history_path, &[])
+}
+
 // Tauri command to check backend health
 #[tauri::command]
-async fn check_backend_health() -> Result<String, String> {
-    let client = reqwest::Client::new();
-    
-    match client.get("http://localhost:8000/health").send().await {
+async fn check_backend_health(state: tauri::State<'_, Mutex<AppState>>) -> Result<String, String> {
+    let (client, backend_url) = {
+        let state = state.lock().map_err(|e| format!("State lock poisoned: {}", e))?;
+        (state.client.clone(), state.backend_url.clone())
+    };
+
+    match client.get(format!("{}/health", backend_url)).send().await {
         Ok(response) => {
             if response.status().is_success() {
                 Ok("Backend is running".to_string())
@@ -85,12 +550,167 @@ async fn check_backend_health() -> Result<String, String> {
     }
 }
 
+// Tauri command letting the frontend block until the backend comes up,
+// e.g. right after launch while the sidecar process is still starting.
+#[tauri::command]
+async fn wait_for_backend_ready(
+    max_attempts: u32,
+    state: tauri::State<'_, Mutex<AppState>>,
+) -> Result<String, String> {
+    let (client, backend_url) = {
+        let state = state.lock().map_err(|e| format!("State lock poisoned: {}", e))?;
+        (state.client.clone(), state.backend_url.clone())
+    };
+
+    wait_for_backend(&client, &backend_url, max_attempts).await
+}
+
+// Tauri command to read the backend URL the app is currently pointed at.
+#[tauri::command]
+fn get_backend_url(state: tauri::State<'_, Mutex<AppState>>) -> Result<String, String> {
+    let state = state.lock().map_err(|e| format!("State lock poisoned: {}", e))?;
+    Ok(state.backend_url.clone())
+}
+
+// Tauri command to repoint the app at a different inference backend, e.g. a
+// remote staging or production server instead of the local Python process.
+#[tauri::command]
+fn set_backend_url(url: String, state: tauri::State<'_, Mutex<AppState>>) -> Result<(), String> {
+    let mut state = state.lock().map_err(|e| format!("State lock poisoned: {}", e))?;
+    state.backend_url = url;
+    Ok(())
+}
+
 fn main() {
+    let cli = Cli::parse();
+    let config = load_config(&cli).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+
+    OPENWEATHER_API_KEY.set(config.openweather_api_key.clone()).ok();
+
+    let client = reqwest::Client::new();
+    let backend_url = config.backend_url.clone();
+    // Only spawn the local sidecar when `backend_url` actually points at this
+    // machine - a remote/staging/production backend should never also have a
+    // stray local Python process spun up and leaked alongside it.
+    let backend_process = if is_local_backend(&backend_url) {
+        spawn_backend_process()
+    } else {
+        None
+    };
+
+    if let Err(e) = tauri::async_runtime::block_on(wait_for_backend(&client, &backend_url, 8)) {
+        eprintln!("Warning: {}", e);
+    }
+
+    let app_state = AppState {
+        backend_url,
+        client,
+        backend_process,
+        history_path: PathBuf::from(HISTORY_FILE_NAME),
+        default_activity: config.default_activity,
+    };
+
     tauri::Builder::default()
+        .manage(Mutex::new(app_state))
+        .setup(|app| {
+            let data_dir = app
+                .path_resolver()
+                .app_data_dir()
+                .unwrap_or_else(|| PathBuf::from("."));
+            std::fs::create_dir_all(&data_dir).ok();
+
+            let state = app.state::<Mutex<AppState>>();
+            state.lock().unwrap().history_path = data_dir.join(HISTORY_FILE_NAME);
+
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             get_weather_prediction,
-            check_backend_health
+            check_backend_health,
+            fetch_current_conditions,
+            forecast_predictions,
+            get_backend_url,
+            set_backend_url,
+            wait_for_backend_ready,
+            get_prediction_history,
+            clear_prediction_history
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::ExitRequested { .. } | tauri::RunEvent::Exit = event {
+                if let Some(state) = app_handle.try_state::<Mutex<AppState>>() {
+                    if let Ok(mut state) = state.lock() {
+                        if let Some(mut child) = state.backend_process.take() {
+                            let _ = child.kill();
+                        }
+                    }
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("weather_predictor_test_{}_{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn load_config_rejects_wrong_typed_field() {
+        let path = temp_path("config");
+        std::fs::write(&path, r#"{"backend_url": 123}"#).unwrap();
+
+        let cli = Cli {
+            config: Some(path.clone()),
+            backend_url: None,
+            api_key: None,
+        };
+
+        let err = load_config(&cli).expect_err("a wrong-typed field should fail to parse");
+        assert!(err.to_string().contains("Invalid config file"));
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn history_round_trips_through_disk() {
+        let path = temp_path("history");
+
+        let entry = HistoryEntry {
+            request: PredictionRequest {
+                temperature: 20.0,
+                humidity: 50.0,
+                wind_speed: 5.0,
+                pressure: 1013.0,
+                activity: "hiking".to_string(),
+            },
+            response: ApiResponse {
+                prediction: WeatherPrediction {
+                    very_hot: 0.1,
+                    very_cold: 0.1,
+                    very_windy: 0.1,
+                    very_wet: 0.1,
+                    very_uncomfortable: 0.1,
+                },
+                activity_risk: "low".to_string(),
+                recommendation: "go for it".to_string(),
+                timestamp: "2026-07-26T00:00:00Z".to_string(),
+            },
+        };
+
+        write_history(&path, &[entry.clone()]).unwrap();
+        let loaded = read_history(&path);
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].request.activity, "hiking");
+        assert_eq!(loaded[0].response.timestamp, "2026-07-26T00:00:00Z");
+
+        std::fs::remove_file(&path).ok();
+    }
 }
\ No newline at end of file